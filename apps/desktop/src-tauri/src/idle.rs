@@ -0,0 +1,98 @@
+// Idle-timeout auto-lock for CoBrain Desktop
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::settings;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+struct IdleState {
+    last_activity: Mutex<Instant>,
+    timeout_seconds: Mutex<Option<u64>>,
+    locked: AtomicBool,
+}
+
+/// Loads the persisted idle timeout and starts the background task that
+/// watches for inactivity.
+pub fn setup<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error::Error>> {
+    let timeout_seconds = settings::load(app.handle()).idle_timeout_seconds;
+
+    app.manage(IdleState {
+        last_activity: Mutex::new(Instant::now()),
+        timeout_seconds: Mutex::new(timeout_seconds),
+        locked: AtomicBool::new(false),
+    });
+
+    if let Some(window) = app.get_webview_window("main") {
+        let app_handle = app.handle().clone();
+        window.on_window_event(move |event| {
+            if matches!(event, tauri::WindowEvent::Focused(true)) {
+                record_activity(&app_handle);
+            }
+        });
+    }
+
+    let app_handle = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            check_idle(&app_handle);
+        }
+    });
+
+    Ok(())
+}
+
+/// Resets the idle clock and clears the locked flag. Called from the window
+/// focus listener above and from capture entry points and IPC commands
+/// elsewhere in the app.
+pub fn record_activity<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(state) = app.try_state::<IdleState>() {
+        *state.last_activity.lock().unwrap() = Instant::now();
+        state.locked.store(false, Ordering::Relaxed);
+    }
+}
+
+fn check_idle<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<IdleState>() else {
+        return;
+    };
+
+    // `None` disables the timeout; `Some(0)` means lock at the next tick.
+    let Some(timeout_seconds) = *state.timeout_seconds.lock().unwrap() else {
+        return;
+    };
+
+    if state.locked.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let idle_for = state.last_activity.lock().unwrap().elapsed();
+    if idle_for >= Duration::from_secs(timeout_seconds) {
+        state.locked.store(true, Ordering::Relaxed);
+
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.hide();
+        }
+        let _ = app.emit("session-locked", ());
+    }
+}
+
+/// Lets the settings page configure (or disable, via `None`) the idle
+/// timeout and persists the choice.
+#[tauri::command]
+pub fn set_idle_timeout(app: AppHandle, seconds: Option<u64>) -> Result<(), String> {
+    record_activity(&app);
+
+    if let Some(state) = app.try_state::<IdleState>() {
+        *state.timeout_seconds.lock().unwrap() = seconds;
+    }
+
+    let mut current_settings = settings::load(&app);
+    current_settings.idle_timeout_seconds = seconds;
+    settings::save(&app, &current_settings)
+}
@@ -3,25 +3,48 @@
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod idle;
+mod media;
+mod settings;
+mod shortcuts;
 mod tray;
+mod updater;
 
 use tauri::Manager;
 
 fn main() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            show_and_focus_main_window(app);
+            if args.iter().any(|arg| arg == "/capture") {
+                open_capture_window(app);
+            }
+        }))
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             Some(vec!["--minimized"]),
         ))
+        .register_asynchronous_uri_scheme_protocol("cobrain", media::handle_request)
         .setup(|app| {
             // Setup system tray
             tray::setup_tray(app)?;
 
-            // Register global shortcut (Ctrl+Shift+Space)
-            setup_global_shortcut(app)?;
+            // Register global shortcuts from stored settings (or defaults)
+            shortcuts::setup(app)?;
+
+            // Start the idle-timeout auto-lock watcher
+            idle::setup(app)?;
+
+            // Silently check for updates at startup; the tray and the
+            // settings page can both trigger the same check on demand.
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = updater::check_and_notify(&app_handle).await;
+            });
 
             // Hide dock icon on macOS when running in background
             #[cfg(target_os = "macos")]
@@ -36,42 +59,59 @@ fn main() {
             hide_main_window,
             open_quick_capture,
             send_notification,
+            updater::check_for_updates,
+            shortcuts::set_global_shortcut,
+            shortcuts::get_global_shortcut,
+            idle::set_idle_timeout,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            // Keep the tray's "Open"/"Hide" item in sync with the main
+            // window's visibility whenever it gains or loses focus.
+            if let tauri::RunEvent::WindowEvent {
+                label,
+                event: tauri::WindowEvent::Focused(_),
+                ..
+            } = event
+            {
+                if label == "main" {
+                    tray::sync_open_item_label(app_handle);
+                }
+            }
+        });
 }
 
-fn setup_global_shortcut(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
+/// Shows and focuses the main window. Shared by the single-instance
+/// callback, the tray's click/menu handlers, global shortcuts, and the
+/// `show_main_window` command so there's one place that knows how to do it.
+pub(crate) fn show_and_focus_main_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    idle::record_activity(app);
 
-    let shortcut = Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::Space);
-
-    let app_handle = app.handle().clone();
-    app.global_shortcut().on_shortcut(shortcut, move |_app, _shortcut, _event| {
-        // Open quick capture window
-        if let Some(window) = app_handle.get_webview_window("main") {
-            let _ = window.show();
-            let _ = window.set_focus();
-            // Navigate to capture page
-            let _ = window.eval("window.location.href = '/capture'");
-        }
-    })?;
-
-    app.global_shortcut().register(shortcut)?;
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
 
-    Ok(())
+/// Shows and focuses the main window, then navigates it to the quick
+/// capture page.
+pub(crate) fn open_capture_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    show_and_focus_main_window(app);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.eval("window.location.href = '/capture'");
+    }
 }
 
 #[tauri::command]
 fn show_main_window(app: tauri::AppHandle) {
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
-    }
+    show_and_focus_main_window(&app);
 }
 
 #[tauri::command]
 fn hide_main_window(app: tauri::AppHandle) {
+    idle::record_activity(&app);
+
     if let Some(window) = app.get_webview_window("main") {
         let _ = window.hide();
     }
@@ -79,27 +119,29 @@ fn hide_main_window(app: tauri::AppHandle) {
 
 #[tauri::command]
 fn open_quick_capture(app: tauri::AppHandle) {
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
-        let _ = window.eval("window.location.href = '/capture'");
-    }
+    open_capture_window(&app);
 }
 
-#[tauri::command]
-async fn send_notification(
-    app: tauri::AppHandle,
-    title: String,
-    body: String,
-) -> Result<(), String> {
+/// Shows a native notification. Shared by the `send_notification` command
+/// and internal flows (like the updater) that need to notify the user
+/// without going through IPC.
+pub(crate) async fn notify(app: &tauri::AppHandle, title: &str, body: &str) -> Result<(), String> {
     use tauri_plugin_notification::NotificationExt;
 
     app.notification()
         .builder()
-        .title(&title)
-        .body(&body)
+        .title(title)
+        .body(body)
         .show()
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())
+}
 
-    Ok(())
+#[tauri::command]
+async fn send_notification(
+    app: tauri::AppHandle,
+    title: String,
+    body: String,
+) -> Result<(), String> {
+    idle::record_activity(&app);
+    notify(&app, &title, &body).await
 }
@@ -0,0 +1,182 @@
+// Custom `cobrain://media/<id>` protocol for streaming captured media
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use tauri::http::{Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, Runtime, UriSchemeContext, UriSchemeResponder};
+
+/// Reads the requested file (optionally a byte range of it) and responds on
+/// a worker thread so blocking file I/O doesn't stall the webview's IPC.
+pub fn handle_request<R: Runtime>(
+    ctx: UriSchemeContext<'_, R>,
+    request: Request<Vec<u8>>,
+    responder: UriSchemeResponder,
+) {
+    let app = ctx.app_handle().clone();
+    std::thread::spawn(move || {
+        responder.respond(respond_to(&app, &request));
+    });
+}
+
+fn respond_to<R: Runtime>(app: &AppHandle<R>, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let Some(id) = media_id(request) else {
+        return error_response(StatusCode::NOT_FOUND);
+    };
+
+    let Some(path) = media_path(app, &id) else {
+        return error_response(StatusCode::NOT_FOUND);
+    };
+
+    let Ok(mut file) = File::open(&path) else {
+        return error_response(StatusCode::NOT_FOUND);
+    };
+
+    let Ok(file_len) = file.metadata().map(|m| m.len()) else {
+        return error_response(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+
+    let range = request
+        .headers()
+        .get("range")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| parse_range(v, file_len));
+
+    match range {
+        Some(Err(())) => Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header("Content-Range", format!("bytes */{file_len}"))
+            .body(Vec::new())
+            .unwrap_or_else(|_| error_response(StatusCode::RANGE_NOT_SATISFIABLE)),
+        Some(Ok((start, end))) => {
+            let len = (end - start + 1) as usize;
+            let mut buf = vec![0u8; len];
+            if file.seek(SeekFrom::Start(start)).is_err() || file.read_exact(&mut buf).is_err() {
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+
+            Response::builder()
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header("Content-Type", content_type(&path))
+                .header("Accept-Ranges", "bytes")
+                .header("Content-Range", format!("bytes {start}-{end}/{file_len}"))
+                .header("Content-Length", len.to_string())
+                .body(buf)
+                .unwrap_or_else(|_| error_response(StatusCode::INTERNAL_SERVER_ERROR))
+        }
+        None => {
+            let mut buf = Vec::with_capacity(file_len as usize);
+            if file.read_to_end(&mut buf).is_err() {
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("Content-Type", content_type(&path))
+                .header("Accept-Ranges", "bytes")
+                .header("Content-Length", buf.len().to_string())
+                .body(buf)
+                .unwrap_or_else(|_| error_response(StatusCode::INTERNAL_SERVER_ERROR))
+        }
+    }
+}
+
+/// Parses a `Range: bytes=start-end` header per RFC 7233, including the
+/// open-ended form (`bytes=1000-`). Returns `None` when there's no range to
+/// honor, `Some(Err(()))` when the range is out of bounds (416).
+fn parse_range(header: &str, file_len: u64) -> Option<Result<(u64, u64), ()>> {
+    let spec = header.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+
+    let start: u64 = start.parse().ok()?;
+    let end = if end.is_empty() {
+        file_len.saturating_sub(1)
+    } else {
+        end.parse().ok()?
+    };
+
+    if file_len == 0 || start >= file_len || start > end {
+        Some(Err(()))
+    } else {
+        Some(Ok((start, end.min(file_len - 1))))
+    }
+}
+
+fn media_id(request: &Request<Vec<u8>>) -> Option<String> {
+    if request.uri().host() != Some("media") {
+        return None;
+    }
+
+    let id = request.uri().path().trim_start_matches('/');
+    if id.is_empty() || id.contains("..") || id.contains('/') || id.contains('\\') {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+fn media_path<R: Runtime>(app: &AppHandle<R>, id: &str) -> Option<PathBuf> {
+    let dir = app.path().app_data_dir().ok()?.join("media");
+    Some(dir.join(id))
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("mp3") => "audio/mpeg",
+        Some("wav") => "audio/wav",
+        Some("m4a") => "audio/mp4",
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("mov") => "video/quicktime",
+        _ => "application/octet-stream",
+    }
+}
+
+fn error_response(status: StatusCode) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .body(Vec::new())
+        .unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_range;
+
+    #[test]
+    fn normal_range() {
+        assert_eq!(parse_range("bytes=100-199", 1000), Some(Ok((100, 199))));
+    }
+
+    #[test]
+    fn open_ended_range() {
+        assert_eq!(parse_range("bytes=1000-", 1500), Some(Ok((1000, 1499))));
+    }
+
+    #[test]
+    fn start_at_or_past_file_len_is_not_satisfiable() {
+        assert_eq!(parse_range("bytes=1000-", 1000), Some(Err(())));
+        assert_eq!(parse_range("bytes=2000-2100", 1000), Some(Err(())));
+    }
+
+    #[test]
+    fn start_after_end_is_not_satisfiable() {
+        assert_eq!(parse_range("bytes=100-50", 1000), Some(Err(())));
+    }
+
+    #[test]
+    fn empty_file_is_never_satisfiable() {
+        assert_eq!(parse_range("bytes=0-", 0), Some(Err(())));
+    }
+
+    #[test]
+    fn end_past_file_len_is_clamped() {
+        assert_eq!(parse_range("bytes=0-999999", 1000), Some(Ok((0, 999))));
+    }
+
+    #[test]
+    fn no_range_header_value_returns_none() {
+        assert_eq!(parse_range("not-a-range-header", 1000), None);
+    }
+}
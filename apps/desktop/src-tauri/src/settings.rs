@@ -0,0 +1,60 @@
+// Persisted user settings for CoBrain Desktop
+
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default = "default_show_shortcut")]
+    pub show_shortcut: String,
+    #[serde(default = "default_capture_shortcut")]
+    pub capture_shortcut: String,
+    /// Seconds of inactivity before the session auto-locks. `None` disables
+    /// the idle timeout entirely.
+    #[serde(default)]
+    pub idle_timeout_seconds: Option<u64>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show_shortcut: default_show_shortcut(),
+            capture_shortcut: default_capture_shortcut(),
+            idle_timeout_seconds: None,
+        }
+    }
+}
+
+fn default_show_shortcut() -> String {
+    "Ctrl+Shift+B".to_string()
+}
+
+fn default_capture_shortcut() -> String {
+    "Ctrl+Shift+Space".to_string()
+}
+
+fn settings_path<R: Runtime>(app: &AppHandle<R>) -> Result<std::path::PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(SETTINGS_FILE))
+}
+
+/// Loads settings from disk, falling back to defaults if the file is
+/// missing or unreadable.
+pub fn load<R: Runtime>(app: &AppHandle<R>) -> Settings {
+    settings_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save<R: Runtime>(app: &AppHandle<R>, settings: &Settings) -> Result<(), String> {
+    let path = settings_path(app)?;
+    let contents = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| e.to_string())
+}
@@ -0,0 +1,152 @@
+// User-configurable global shortcuts for CoBrain Desktop
+
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState as PressState};
+
+use crate::settings::{self, Settings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    ShowMainWindow,
+    QuickCapture,
+}
+
+impl ShortcutAction {
+    fn parse(id: &str) -> Result<Self, String> {
+        match id {
+            "show_main_window" => Ok(Self::ShowMainWindow),
+            "quick_capture" => Ok(Self::QuickCapture),
+            other => Err(format!("unknown shortcut action: {other}")),
+        }
+    }
+}
+
+struct ShortcutSlots {
+    show: Mutex<Option<Shortcut>>,
+    capture: Mutex<Option<Shortcut>>,
+}
+
+/// Reads the stored shortcut settings and registers both accelerators at
+/// startup. A persisted accelerator that's invalid or can't be registered
+/// falls back to the built-in default rather than taking the app down.
+pub fn setup<R: Runtime>(app: &tauri::App<R>) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(ShortcutSlots {
+        show: Mutex::new(None),
+        capture: Mutex::new(None),
+    });
+
+    let settings = settings::load(app.handle());
+    register_or_default(app.handle(), ShortcutAction::ShowMainWindow, &settings.show_shortcut);
+    register_or_default(app.handle(), ShortcutAction::QuickCapture, &settings.capture_shortcut);
+
+    Ok(())
+}
+
+/// Registers `accelerator` for `action`, falling back to the built-in
+/// default if it's invalid or the OS refuses to register it (e.g.
+/// `settings.json` was hand-edited with a typo), so a bad value in it is
+/// never fatal.
+fn register_or_default<R: Runtime>(app: &AppHandle<R>, action: ShortcutAction, accelerator: &str) {
+    if let Err(e) = register(app, action, accelerator) {
+        let default = shortcut_for(&Settings::default(), action);
+        eprintln!(
+            "failed to register {action:?} shortcut '{accelerator}': {e}; falling back to default '{default}'"
+        );
+
+        if let Err(e) = register(app, action, &default) {
+            eprintln!("failed to register default {action:?} shortcut '{default}': {e}");
+        }
+    }
+}
+
+/// Parses and registers the new accelerator, wiring up its handler, and
+/// only then unregisters the action's previous shortcut (if any) and
+/// updates the tracked slot. If registration of the new accelerator fails
+/// (e.g. it's already bound by another app), the previous shortcut is left
+/// registered and untouched.
+fn register<R: Runtime>(
+    app: &AppHandle<R>,
+    action: ShortcutAction,
+    accelerator: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let shortcut = Shortcut::from_str(accelerator)?;
+
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state() != PressState::Pressed {
+                return;
+            }
+
+            match action {
+                ShortcutAction::ShowMainWindow => crate::show_and_focus_main_window(app),
+                ShortcutAction::QuickCapture => crate::open_capture_window(app),
+            }
+        })?;
+
+    if let Err(e) = app.global_shortcut().register(shortcut) {
+        // The handler above is only live once registration succeeds; tear
+        // it down so it doesn't linger for an accelerator that never got
+        // registered.
+        let _ = app.global_shortcut().unregister(shortcut);
+        return Err(e.into());
+    }
+
+    let slots = app.state::<ShortcutSlots>();
+    let slot = match action {
+        ShortcutAction::ShowMainWindow => &slots.show,
+        ShortcutAction::QuickCapture => &slots.capture,
+    };
+
+    if let Some(previous) = slot.lock().unwrap().replace(shortcut) {
+        if previous != shortcut {
+            let _ = app.global_shortcut().unregister(previous);
+        }
+    }
+
+    Ok(())
+}
+
+fn shortcut_for(settings: &Settings, action: ShortcutAction) -> String {
+    match action {
+        ShortcutAction::ShowMainWindow => settings.show_shortcut.clone(),
+        ShortcutAction::QuickCapture => settings.capture_shortcut.clone(),
+    }
+}
+
+fn set_shortcut_for(settings: &mut Settings, action: ShortcutAction, accelerator: String) {
+    match action {
+        ShortcutAction::ShowMainWindow => settings.show_shortcut = accelerator,
+        ShortcutAction::QuickCapture => settings.capture_shortcut = accelerator,
+    }
+}
+
+/// Rebinds one of the app's global shortcuts and persists the new
+/// accelerator so it's restored on the next launch.
+#[tauri::command]
+pub fn set_global_shortcut(app: AppHandle, action: String, accelerator: String) -> Result<(), String> {
+    crate::idle::record_activity(&app);
+
+    let action = ShortcutAction::parse(&action)?;
+    register(&app, action, &accelerator).map_err(|e| e.to_string())?;
+
+    let mut settings = settings::load(&app);
+    set_shortcut_for(&mut settings, action, accelerator);
+    settings::save(&app, &settings)?;
+
+    if action == ShortcutAction::QuickCapture {
+        crate::tray::sync_capture_item_accelerator(&app);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_global_shortcut(app: AppHandle, action: String) -> Result<String, String> {
+    crate::idle::record_activity(&app);
+
+    let action = ShortcutAction::parse(&action)?;
+    Ok(shortcut_for(&settings::load(&app), action))
+}
@@ -1,35 +1,47 @@
 // System Tray Implementation for CoBrain Desktop
 
+use std::sync::Mutex;
+
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{TrayIcon, TrayIconBuilder, TrayIconEvent},
-    Manager, Runtime,
+    AppHandle, Manager, Runtime,
 };
 
-pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<TrayIcon<R>, Box<dyn std::error::Error>> {
-    let open_item = MenuItem::with_id(app, "open", "Open CoBrain", true, None::<&str>)?;
-    let capture_item = MenuItem::with_id(app, "capture", "Quick Capture", true, Some("Ctrl+Shift+Space"))?;
-    let separator = MenuItem::with_id(app, "sep", "-", false, None::<&str>)?;
-    let quit_item = MenuItem::with_id(app, "quit", "Quit", true, Some("Ctrl+Q"))?;
+/// Tracks the tray's state-aware menu items so focus listeners and click
+/// handlers outside `setup_tray` can keep them in sync.
+struct TrayState<R: Runtime> {
+    open_item: Mutex<MenuItem<R>>,
+    capture_item: Mutex<MenuItem<R>>,
+}
 
-    let menu = Menu::with_items(app, &[&open_item, &capture_item, &separator, &quit_item])?;
+pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<TrayIcon<R>, Box<dyn std::error::Error>> {
+    let open_item = MenuItem::with_id(app, "open", open_item_label(app.handle()), true, None::<&str>)?;
+    let capture_item = MenuItem::with_id(
+        app,
+        "capture",
+        "Quick Capture",
+        true,
+        Some(capture_shortcut_hint(app.handle())),
+    )?;
+    let menu = build_context_menu(app.handle(), open_item.clone(), capture_item.clone())?;
+    app.manage(TrayState {
+        open_item: Mutex::new(open_item),
+        capture_item: Mutex::new(capture_item),
+    });
 
     let tray = TrayIconBuilder::new()
         .menu(&menu)
+        .show_menu_on_left_click(false)
         .tooltip("CoBrain - Your AI Second Brain")
         .on_menu_event(move |app, event| match event.id.as_ref() {
-            "open" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
-            }
-            "capture" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                    let _ = window.eval("window.location.href = '/capture'");
-                }
+            "open" => toggle_main_window(app),
+            "capture" => crate::open_capture_window(app),
+            "check_updates" => {
+                let app_handle = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let _ = crate::updater::check_and_notify(&app_handle).await;
+                });
             }
             "quit" => {
                 app.exit(0);
@@ -37,15 +49,110 @@ pub fn setup_tray<R: Runtime>(app: &tauri::App<R>) -> Result<TrayIcon<R>, Box<dy
             _ => {}
         })
         .on_tray_icon_event(|tray, event| {
-            if let TrayIconEvent::Click { button: tauri::tray::MouseButton::Left, .. } = event {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
+            let app = tray.app_handle();
+            match event {
+                TrayIconEvent::Click {
+                    button: tauri::tray::MouseButton::Left,
+                    button_state: tauri::tray::MouseButtonState::Up,
+                    ..
+                } => {
+                    toggle_main_window(app);
                 }
+                TrayIconEvent::Click {
+                    button: tauri::tray::MouseButton::Right,
+                    button_state: tauri::tray::MouseButtonState::Up,
+                    ..
+                } => {
+                    // Rebuild the context menu on demand so it reflects the
+                    // current window visibility rather than the state the
+                    // tray was created with.
+                    sync_open_item_label(app);
+                    if let Some(state) = app.try_state::<TrayState<R>>() {
+                        let open_item = state.open_item.lock().unwrap().clone();
+                        let capture_item = state.capture_item.lock().unwrap().clone();
+                        if let Ok(menu) = build_context_menu(app, open_item, capture_item) {
+                            let _ = tray.set_menu(Some(menu));
+                        }
+                    }
+                }
+                _ => {}
             }
         })
         .build(app)?;
 
     Ok(tray)
 }
+
+/// Builds the tray's menu from scratch, reusing the state-aware `open_item`
+/// and `capture_item` passed in and creating fresh copies of the rest. Used
+/// both for the initial tray menu and to rebuild the right-click context
+/// menu on demand.
+fn build_context_menu<R: Runtime>(
+    app: &AppHandle<R>,
+    open_item: MenuItem<R>,
+    capture_item: MenuItem<R>,
+) -> tauri::Result<Menu<R>> {
+    let check_updates_item = MenuItem::with_id(app, "check_updates", "Check for Updates", true, None::<&str>)?;
+    let separator = MenuItem::with_id(app, "sep", "-", false, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, "quit", "Quit", true, Some("Ctrl+Q"))?;
+
+    Menu::with_items(
+        app,
+        &[&open_item, &capture_item, &check_updates_item, &separator, &quit_item],
+    )
+}
+
+/// The current "Quick Capture" accelerator hint, read from settings so it
+/// never drifts from the shortcut the user actually bound.
+fn capture_shortcut_hint<R: Runtime>(app: &AppHandle<R>) -> String {
+    crate::settings::load(app).capture_shortcut
+}
+
+fn open_item_label<R: Runtime>(app: &AppHandle<R>) -> &'static str {
+    let visible = app
+        .get_webview_window("main")
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+
+    if visible {
+        "Hide CoBrain"
+    } else {
+        "Open CoBrain"
+    }
+}
+
+fn toggle_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let visible = app
+        .get_webview_window("main")
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false);
+
+    if visible {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.hide();
+        }
+    } else {
+        crate::show_and_focus_main_window(app);
+    }
+
+    sync_open_item_label(app);
+}
+
+/// Flips the tray's "Open CoBrain" / "Hide CoBrain" item to match whether
+/// the main window is currently visible. Called after any toggle and from
+/// the `RunEvent::WindowEvent` focus listener in `main.rs`.
+pub fn sync_open_item_label<R: Runtime>(app: &AppHandle<R>) {
+    let label = open_item_label(app);
+    if let Some(state) = app.try_state::<TrayState<R>>() {
+        let _ = state.open_item.lock().unwrap().set_text(label);
+    }
+}
+
+/// Refreshes the "Quick Capture" item's accelerator hint to match the
+/// currently bound shortcut. Called after `set_global_shortcut` rebinds it.
+pub fn sync_capture_item_accelerator<R: Runtime>(app: &AppHandle<R>) {
+    let hint = capture_shortcut_hint(app);
+    if let Some(state) = app.try_state::<TrayState<R>>() {
+        let _ = state.capture_item.lock().unwrap().set_accelerator(Some(hint));
+    }
+}
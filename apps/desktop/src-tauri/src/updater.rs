@@ -0,0 +1,44 @@
+// Auto-update handling for CoBrain Desktop
+
+use tauri::AppHandle;
+use tauri_plugin_updater::UpdaterExt;
+
+use crate::notify;
+
+/// Queries the configured update endpoint and, if a newer version is
+/// available, notifies the user and kicks off the download/install in the
+/// background. Returns the available version (if any); the
+/// `check_for_updates` command passes it on to the frontend, while the tray
+/// menu and the startup check ignore it.
+pub async fn check_and_notify(app: &AppHandle) -> Result<Option<String>, String> {
+    let updater = app.updater().map_err(|e| e.to_string())?;
+
+    match updater.check().await.map_err(|e| e.to_string())? {
+        Some(update) => {
+            let version = update.version.clone();
+
+            let _ = notify(
+                app,
+                "Update available",
+                &format!("CoBrain {version} is available. Downloading now..."),
+            )
+            .await;
+
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = update.download_and_install(|_, _| {}, || {}).await {
+                    eprintln!("failed to download and install update: {e}");
+                }
+            });
+
+            Ok(Some(version))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Lets the frontend settings page trigger a check on demand.
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<Option<String>, String> {
+    crate::idle::record_activity(&app);
+    check_and_notify(&app).await
+}